@@ -35,6 +35,9 @@ use python_spy::PythonSpy;
 use stack_trace::StackTrace;
 use console_viewer::ConsoleViewer;
 
+#[cfg(target_os = "macos")]
+use std::os::unix::process::ExitStatusExt;
+
 fn print_traces(traces: &[StackTrace], show_idle: bool) {
     for trace in traces {
         if !show_idle && !trace.active {
@@ -63,6 +66,84 @@ fn process_exitted(err: &Error) -> bool {
     })
 }
 
+// Checks whether `pid` is still alive without relying on the error codes of a failed
+// read. On Linux we poll a pidfd, which becomes readable the instant the process exits
+// (no PID-reuse races, no guessing). On macOS/BSD we get the same guarantee from a
+// one-shot kqueue EVFILT_PROC/NOTE_EXIT watch. Anywhere else, fall back to
+// `process_exitted`'s io-error-code heuristic.
+#[cfg(target_os = "linux")]
+fn is_process_alive(pid: u32) -> bool {
+    unsafe {
+        let fd = libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0);
+        if fd < 0 {
+            // ESRCH means the pid is already gone; any other errno we just can't tell
+            return std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH);
+        }
+        let fd = fd as libc::c_int;
+        let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+        let readable = libc::poll(&mut pollfd, 1, 0) > 0 && (pollfd.revents & libc::POLLIN) != 0;
+        libc::close(fd);
+        !readable
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "dragonfly",
+          target_os = "openbsd", target_os = "netbsd"))]
+fn is_process_alive(pid: u32) -> bool {
+    unsafe {
+        let kq = libc::kqueue();
+        if kq < 0 {
+            return true;
+        }
+
+        let mut change: libc::kevent = std::mem::zeroed();
+        change.ident = pid as libc::uintptr_t;
+        change.filter = libc::EVFILT_PROC;
+        change.flags = libc::EV_ADD | libc::EV_ONESHOT;
+        change.fflags = libc::NOTE_EXIT;
+
+        let mut event: libc::kevent = std::mem::zeroed();
+        let timeout = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        let fired = libc::kevent(kq, &change, 1, &mut event, 1, &timeout) > 0;
+        libc::close(kq);
+        // registering the watch fails outright (ESRCH) if the process is already gone
+        !fired
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd",
+              target_os = "dragonfly", target_os = "openbsd", target_os = "netbsd")))]
+fn is_process_alive(_pid: u32) -> bool {
+    // no definitive liveness check on this platform: callers fall back to process_exitted
+    true
+}
+
+// Maps a child's ExitStatus to the code py-spy itself should exit with, decoding
+// signal-termination into 128 + signo the way shells (and `time`, `strace`, ...) do.
+fn child_exit_code(status: std::process::ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    1
+}
+
+// Like `child_exit_code`, but for a child we killed ourselves (e.g. because --flame hit
+// its sample limit while the target was still running): that's py-spy ending sampling
+// successfully, not the child failing, so report success rather than the kill signal.
+fn finished_exit_code(status: std::process::ExitStatus, killed_by_us: bool) -> i32 {
+    if killed_by_us {
+        return 0;
+    }
+    child_exit_code(status)
+}
+
 fn permission_denied(err: &Error) -> bool {
     err.causes().any(|cause| {
         if let Some(ioerror) = cause.downcast_ref::<std::io::Error>() {
@@ -80,22 +161,17 @@ fn sample_console(process: &PythonSpy,
     let mut console = ConsoleViewer::new(show_idle, display, &format!("{}", process.version))?;
 
     let mut elapsed = 0;
-    let mut exitted_count = 0;
     loop {
         match process.get_stack_traces() {
             Ok(traces) => {
                 console.increment(&traces);
             },
             Err(err) => {
-                if process_exitted(&err) {
-                    exitted_count += 1;
-                    if exitted_count > 5 {
-                        println!("process {} ended", process.pid);
-                        break;
-                    }
-                } else {
-                    console.increment_error(&err);
+                if !is_process_alive(process.pid) || process_exitted(&err) {
+                    println!("process {} ended", process.pid);
+                    break;
                 }
+                console.increment_error(&err);
             }
         }
         if console.should_refresh() || elapsed >= 1000  {
@@ -118,7 +194,6 @@ fn sample_flame(process: &PythonSpy, filename: &str) -> Result<(), Error> {
     println!("Taking {} samples of process", max_samples);
     let mut errors = 0;
     let mut samples = 0;
-    let mut exitted_count = 0;
     for _ in 0..max_samples {
         match process.get_stack_traces() {
             Ok(traces) => {
@@ -126,13 +201,9 @@ fn sample_flame(process: &PythonSpy, filename: &str) -> Result<(), Error> {
                 samples += 1;
             },
             Err(err) => {
-                if process_exitted(&err) {
-                    exitted_count += 1;
-                    // there must be a better way to figure out if the process is still running
-                    if exitted_count > 3 {
-                        println!("process {} ended", process.pid);
-                        break;
-                    }
+                if !is_process_alive(process.pid) || process_exitted(&err) {
+                    println!("process {} ended", process.pid);
+                    break;
                 }
                 errors += 1;
             }
@@ -154,7 +225,248 @@ fn sample_flame(process: &PythonSpy, filename: &str) -> Result<(), Error> {
     Ok(())
 }
 
-fn pyspy_main() -> Result<(), Error> {
+// A child process that was launched stopped right after exec, so that py-spy gets to
+// resume (and start attaching) the instant the new program image exists, rather than
+// racing it from the moment `Command::spawn`/`posix_spawn` returns. On Linux this is a
+// regular `std::process::Child` that called `PTRACE_TRACEME` in `pre_exec`, which the
+// kernel turns into a SIGTRAP-stop the instant `execvp` actually replaces the process
+// image (unlike `raise(SIGSTOP)`, which would stop the child *before* exec and so
+// deadlock `Command::spawn()` -- see `spawn_suspended` below). On macOS it's a bare pid
+// obtained via `posix_spawn` with `POSIX_SPAWN_START_SUSPENDED` instead. Note that this
+// only pins down the *exec* race: `retry_new` still needs the dynamic linker to map
+// libpython in before it can find an interpreter, so we resume before attaching and
+// can't sample frames from before that point.
+#[cfg(unix)]
+enum SuspendedChild {
+    Child(std::process::Child),
+    #[cfg(target_os = "macos")]
+    Pid { pid: libc::pid_t, stderr: Option<std::fs::File> },
+}
+
+#[cfg(unix)]
+impl SuspendedChild {
+    fn id(&self) -> u32 {
+        match self {
+            SuspendedChild::Child(child) => child.id(),
+            #[cfg(target_os = "macos")]
+            SuspendedChild::Pid { pid, .. } => *pid as u32,
+        }
+    }
+
+    // Resumes the process that was stopped at startup. Called right after spawning,
+    // before py-spy attaches, so the process runs its startup code unsampled.
+    fn resume(&self) -> std::io::Result<()> {
+        match self {
+            #[cfg(target_os = "linux")]
+            SuspendedChild::Child(child) => {
+                // we're the tracer (PTRACE_TRACEME in the child's pre_exec): detaching
+                // lets it run on as a normal, untraced process again.
+                let pid = child.id() as libc::pid_t;
+                let ret = unsafe {
+                    libc::ptrace(libc::PTRACE_DETACH, pid,
+                                 std::ptr::null_mut::<libc::c_void>(),
+                                 std::ptr::null_mut::<libc::c_void>())
+                };
+                if ret != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            },
+            #[cfg(not(target_os = "linux"))]
+            SuspendedChild::Child(child) => {
+                if unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGCONT) } != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            },
+            #[cfg(target_os = "macos")]
+            SuspendedChild::Pid { pid, .. } => {
+                if unsafe { libc::kill(*pid, libc::SIGCONT) } != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            },
+        }
+    }
+
+    // Hands back the buffered stderr pipe when we were spawned with capture_output,
+    // for the failure-diagnostics path. Returns None when we're just streaming the
+    // subprocess's output straight to the terminal instead.
+    fn take_stderr(&mut self) -> Option<Box<dyn std::io::Read + Send>> {
+        match self {
+            SuspendedChild::Child(child) => {
+                child.stderr.take().map(|s| Box::new(s) as Box<dyn std::io::Read + Send>)
+            },
+            #[cfg(target_os = "macos")]
+            SuspendedChild::Pid { stderr, .. } => {
+                stderr.take().map(|s| Box::new(s) as Box<dyn std::io::Read + Send>)
+            },
+        }
+    }
+
+    // Finds out how the child actually finished, killing it first if it's still
+    // running rather than blocking here for however long it would take to exit on its
+    // own (e.g. because we hit --flame's sample limit before it exited). The second
+    // element of the result says whether that happened, so callers don't mistake a
+    // signal *we* sent for the child's own failure.
+    fn finish(&mut self) -> std::io::Result<(std::process::ExitStatus, bool)> {
+        match self {
+            SuspendedChild::Child(child) => {
+                match child.try_wait()? {
+                    Some(status) => Ok((status, false)),
+                    None => {
+                        child.kill().ok();
+                        Ok((child.wait()?, true))
+                    }
+                }
+            },
+            #[cfg(target_os = "macos")]
+            SuspendedChild::Pid { pid, .. } => {
+                let pid = *pid;
+                let mut wstatus: libc::c_int = 0;
+                if unsafe { libc::waitpid(pid, &mut wstatus, libc::WNOHANG) } == pid {
+                    return Ok((std::process::ExitStatus::from_raw(wstatus), false));
+                }
+                unsafe { libc::kill(pid, libc::SIGKILL); }
+                if unsafe { libc::waitpid(pid, &mut wstatus, 0) } < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok((std::process::ExitStatus::from_raw(wstatus), true))
+            },
+        }
+    }
+}
+
+// Spawns `subprocess` stopped right after exec, before any of its startup code has run.
+// The caller resumes it before attaching, so py-spy is not attached while that startup
+// code runs. By default the child's stdout/stderr are inherited
+// so interactive programs and progress bars keep working; with `capture_output` they're
+// buffered instead (stdout dropped, stderr kept around for the failure-diagnostics
+// path) the way py-spy used to behave unconditionally.
+#[cfg(unix)]
+fn spawn_suspended(subprocess: &[&str], capture_output: bool) -> std::io::Result<SuspendedChild> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::process::CommandExt;
+        let mut command = std::process::Command::new(subprocess[0]);
+        command.args(&subprocess[1..]).stdin(std::process::Stdio::null());
+        if capture_output {
+            command.stdout(std::process::Stdio::null()).stderr(std::process::Stdio::piped());
+        } else {
+            command.stdout(std::process::Stdio::inherit()).stderr(std::process::Stdio::inherit());
+        }
+
+        unsafe {
+            command.pre_exec(|| {
+                // PTRACE_TRACEME doesn't stop us itself -- it just arranges for the
+                // kernel to deliver a SIGTRAP-stop right after the upcoming execvp()
+                // replaces our image. That's well past the fork-without-exec window, so
+                // Command::spawn() still sees a normal successful exec and returns.
+                if libc::ptrace(libc::PTRACE_TRACEME, 0,
+                                 std::ptr::null_mut::<libc::c_void>(),
+                                 std::ptr::null_mut::<libc::c_void>()) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let child = command.spawn()?;
+
+        // spawn() only guarantees exec succeeded, not that the resulting exec-stop has
+        // already been delivered: synchronize on it so we never attach before the new
+        // program (and libpython) is actually mapped in.
+        let mut wstatus: libc::c_int = 0;
+        if unsafe { libc::waitpid(child.id() as libc::pid_t, &mut wstatus, libc::WUNTRACED) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(SuspendedChild::Child(child))
+    }
+
+    // PTRACE_TRACEME's exec-stop semantics (and the constants for it) aren't portable
+    // across the other unix-family platforms (the BSDs and beyond), so rather than ship
+    // another subtly-wrong suspend here, decline until someone wires up the equivalent
+    // for each one.
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = (subprocess, capture_output);
+        Err(std::io::Error::new(std::io::ErrorKind::Other,
+            "--stop-at-start isn't implemented on this platform yet"))
+    }
+
+    // On OSX, prefer posix_spawn with POSIX_SPAWN_START_SUSPENDED over pre_exec: we
+    // already require root here, and this runs none of our own code between fork and
+    // exec in the child.
+    #[cfg(target_os = "macos")]
+    {
+        use std::ffi::CString;
+        use std::os::unix::io::FromRawFd;
+
+        // Not exposed by the libc crate: see <spawn.h> on OSX.
+        const POSIX_SPAWN_START_SUSPENDED: libc::c_short = 0x0080;
+
+        let path = CString::new(subprocess[0])?;
+        let args: Vec<CString> = subprocess.iter()
+            .map(|arg| CString::new(*arg).expect("invalid argument"))
+            .collect();
+        let mut argv: Vec<*mut libc::c_char> = args.iter()
+            .map(|arg| arg.as_ptr() as *mut libc::c_char)
+            .collect();
+        argv.push(std::ptr::null_mut());
+
+        unsafe {
+            let mut attr: libc::posix_spawnattr_t = std::mem::zeroed();
+            libc::posix_spawnattr_init(&mut attr);
+            libc::posix_spawnattr_setflags(&mut attr, POSIX_SPAWN_START_SUSPENDED);
+
+            // With no file actions the child simply inherits our stdout/stderr, which
+            // is exactly the default (streaming) behavior we want; capture_output adds
+            // actions that redirect stdout to /dev/null and stderr into a pipe instead.
+            let mut file_actions: libc::posix_spawn_file_actions_t = std::mem::zeroed();
+            libc::posix_spawn_file_actions_init(&mut file_actions);
+
+            let mut stderr_pipe = None;
+            if capture_output {
+                let mut fds = [0 as libc::c_int; 2];
+                if libc::pipe(fds.as_mut_ptr()) != 0 {
+                    let err = std::io::Error::last_os_error();
+                    libc::posix_spawnattr_destroy(&mut attr);
+                    libc::posix_spawn_file_actions_destroy(&mut file_actions);
+                    return Err(err);
+                }
+                let (read_fd, write_fd) = (fds[0], fds[1]);
+                let dev_null = CString::new("/dev/null").unwrap();
+                libc::posix_spawn_file_actions_addopen(
+                    &mut file_actions, libc::STDOUT_FILENO, dev_null.as_ptr(), libc::O_WRONLY, 0);
+                libc::posix_spawn_file_actions_adddup2(&mut file_actions, write_fd, libc::STDERR_FILENO);
+                libc::posix_spawn_file_actions_addclose(&mut file_actions, write_fd);
+                libc::posix_spawn_file_actions_addclose(&mut file_actions, read_fd);
+                stderr_pipe = Some((read_fd, write_fd));
+            }
+
+            let mut pid: libc::pid_t = 0;
+            let ret = libc::posix_spawn(&mut pid, path.as_ptr(), &file_actions, &attr,
+                                         argv.as_mut_ptr(), libc::environ);
+            libc::posix_spawnattr_destroy(&mut attr);
+            libc::posix_spawn_file_actions_destroy(&mut file_actions);
+
+            let stderr = stderr_pipe.map(|(read_fd, write_fd)| {
+                libc::close(write_fd);
+                std::fs::File::from_raw_fd(read_fd)
+            });
+
+            if ret != 0 {
+                return Err(std::io::Error::from_raw_os_error(ret));
+            }
+            Ok(SuspendedChild::Pid { pid, stderr })
+        }
+    }
+}
+
+// Returns the process exit code py-spy itself should exit with: 0 when attaching to
+// an already-running pid, or the profiled subprocess's own exit code when py-spy is
+// the one that launched it (0 if py-spy killed it because it was still running when
+// sampling ended).
+fn pyspy_main() -> Result<i32, Error> {
     let matches = App::new("py-spy")
         .about("Spies on python programs!")
         .arg(Arg::with_name("pid")
@@ -178,6 +490,22 @@ fn pyspy_main() -> Result<(), Error> {
             .help("commandline of a python program to run")
             .multiple(true)
             )
+        .arg(Arg::with_name("stop_at_start")
+            .long("stop-at-start")
+            .requires("python_program")
+            .help("Stop the subprocess right after it execs, then resume it and attach. \
+                   This avoids the race where py-spy starts attaching before the \
+                   subprocess even exists yet (though frames from before the \
+                   interpreter finishes loading still can't be sampled)"))
+        .arg(Arg::with_name("capture_output")
+            .long("capture-output")
+            .requires("python_program")
+            .help("Capture the subprocess's stdout/stderr instead of letting it stream \
+                   straight through to the terminal, printing stderr only if something \
+                   goes wrong. By default the subprocess's output is inherited so \
+                   interactive programs and progress bars still work when generating a \
+                   flame graph; output is always captured instead when live-sampling to \
+                   the console, so it doesn't get drawn over"))
         .get_matches();
 
     if let Some(pid_str) = matches.value_of("pid") {
@@ -198,12 +526,93 @@ fn pyspy_main() -> Result<(), Error> {
 
     else if let Some(subprocess) = matches.values_of("python_program") {
         let subprocess: Vec<&str> = subprocess.collect();
-        let mut command = std::process::Command::new(subprocess[0])
-            .args(&subprocess[1..])
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .spawn()?;
+
+        if matches.occurrences_of("stop_at_start") > 0 {
+            #[cfg(not(unix))]
+            {
+                return Err(format_err!("--stop-at-start isn't supported on this platform yet"));
+            }
+
+            #[cfg(unix)]
+            {
+                // The console viewer owns the terminal while it's live-sampling, so
+                // letting the child's output land on the same tty would scribble over
+                // its UI; only a flame-graph run (which doesn't draw anything) gets the
+                // streaming-by-default behavior `--capture-output` describes.
+                let capture_output = matches.occurrences_of("capture_output") > 0
+                    || matches.value_of("flame").is_none();
+                let mut child = spawn_suspended(&subprocess, capture_output)?;
+
+                // Resume right away: retry_new needs libpython actually mapped in to
+                // find an interpreter, and that can't happen while we're still sitting
+                // on the post-exec trace-stop, before the dynamic linker has even run.
+                // We still attach as early after exec as we can -- we just can't sample
+                // frames from before the loader finishes.
+                child.resume().ok();
+
+                match PythonSpy::retry_new(child.id(), 3) {
+                    Ok(process) => {
+                        let result = if let Some(flame_file) = matches.value_of("flame") {
+                            sample_flame(&process, flame_file)
+                        } else {
+                            sample_console(&process, &subprocess.join(" "), false)
+                        };
+
+                        // find out how the child actually finished (killing it first if
+                        // it's still running) before we report a result
+                        let (status, killed_by_us) = child.finish()?;
+
+                        // if we captured the subprocess's output and something went
+                        // wrong, dump out stderr here (could have a useful error message)
+                        if capture_output && (!status.success() || result.is_err()) {
+                            if let Some(mut stderr) = child.take_stderr() {
+                                std::thread::spawn(move || {
+                                    let mut buffer = String::new();
+                                    if let Ok(_) = stderr.read_to_string(&mut buffer) {
+                                        eprintln!("{}", buffer);
+                                    }
+                                });
+                                std::thread::sleep(std::time::Duration::from_millis(20));
+                            }
+                        }
+
+                        result?;
+                        return Ok(finished_exit_code(status, killed_by_us));
+                    },
+                    Err(e) => {
+                        // we never managed to attach: the subprocess itself isn't at
+                        // fault, and it's already running (we resumed it above), so let
+                        // it carry on rather than killing the user's program over a
+                        // py-spy-side attach failure.
+                        if capture_output {
+                            if let Some(mut stderr) = child.take_stderr() {
+                                std::thread::spawn(move || {
+                                    let mut buffer = String::new();
+                                    if let Ok(_) = stderr.read_to_string(&mut buffer) {
+                                        eprintln!("{}", buffer);
+                                    }
+                                });
+                                std::thread::sleep(std::time::Duration::from_millis(20));
+                            }
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        // Same reasoning as above: only let the child's output stream straight to the
+        // terminal when we're not drawing the console viewer on top of it.
+        let capture_output = matches.occurrences_of("capture_output") > 0
+            || matches.value_of("flame").is_none();
+        let mut command = std::process::Command::new(subprocess[0]);
+        command.args(&subprocess[1..]).stdin(std::process::Stdio::null());
+        if capture_output {
+            command.stdout(std::process::Stdio::null()).stderr(std::process::Stdio::piped());
+        } else {
+            command.stdout(std::process::Stdio::inherit()).stderr(std::process::Stdio::inherit());
+        }
+        let mut command = command.spawn()?;
 
         #[cfg(target_os="macos")]
         {
@@ -221,17 +630,22 @@ fn pyspy_main() -> Result<(), Error> {
             Err(e) => Err(e)
         };
 
-        // check exit code of subprocess
+        // find out how the subprocess actually finished. If it's still running (e.g. we
+        // hit --flame's sample limit before it exited), kill it and reap the real
+        // (signalled) exit status rather than blocking here for however long it would
+        // take to exit on its own.
         std::thread::sleep(std::time::Duration::from_millis(1));
-        let success =  match command.try_wait()? {
-            Some(exit) => exit.success(),
-            // if process hasn't finished, assume success
-            None => true
+        let (status, killed_by_us) = match command.try_wait()? {
+            Some(status) => (status, false),
+            None => {
+                command.kill().ok();
+                (command.wait()?, true)
+            }
         };
 
-        // if we failed for any reason, dump out stderr from child process here
-        // (could have useful error message)
-        if !success || result.is_err() {
+        // if we captured the subprocess's output and something went wrong, dump out
+        // stderr here (could have a useful error message)
+        if capture_output && (!status.success() || result.is_err()) {
             // Read from stderr in a thread to avoid blocking here (in case we have
             // error but no output on stderr in child process).
             let mut stderr = command.stderr.take().unwrap();
@@ -245,15 +659,11 @@ fn pyspy_main() -> Result<(), Error> {
             std::thread::sleep(std::time::Duration::from_millis(20));
         }
 
-        // kill it so we don't have dangling processess
-        if let Err(_) = command.kill() {
-            // I don't actually care if we failed to kill ... most times process is already done
-            // eprintln!("Error killing child process {}", e);
-        }
-        return result;
+        result?;
+        return Ok(finished_exit_code(status, killed_by_us));
     }
 
-    Ok(())
+    Ok(0)
 }
 
 fn main() {
@@ -266,19 +676,22 @@ fn main() {
         }
     }
 
-    if let Err(err) = pyspy_main() {
-        if permission_denied(&err) {
-            eprintln!("Permission Denied: Try running again with elevated permissions by going 'sudo env \"PATH=$PATH\" !!'");
-            std::process::exit(1);
-        }
+    match pyspy_main() {
+        Ok(exit_code) => std::process::exit(exit_code),
+        Err(err) => {
+            if permission_denied(&err) {
+                eprintln!("Permission Denied: Try running again with elevated permissions by going 'sudo env \"PATH=$PATH\" !!'");
+                std::process::exit(1);
+            }
 
-        eprintln!("Error: {}", err);
-        for (i, suberror) in err.causes().enumerate() {
-            if i > 0 {
-                eprintln!("Reason: {}", suberror);
+            eprintln!("Error: {}", err);
+            for (i, suberror) in err.causes().enumerate() {
+                if i > 0 {
+                    eprintln!("Reason: {}", suberror);
+                }
             }
+            eprintln!("{}", err.backtrace());
+            std::process::exit(1);
         }
-        eprintln!("{}", err.backtrace());
-        std::process::exit(1);
     }
 }
\ No newline at end of file